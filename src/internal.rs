@@ -3,95 +3,259 @@ use std::mem::size_of;
 
 use indexmap::IndexMap;
 use memflow::types::umem;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyTuple};
+use pyo3::types::{PyBytes, PyDict, PyTuple};
 
 use crate::MemflowPyError;
 
+// Dedicated Python exception for a failed `py_from_bytes`/`py_to_bytes` conversion.
+create_exception!(memflow_py, MemflowPyException, PyException);
+
+/// Byte order a scalar or composite value should be interpreted with.
+///
+/// Borrowed from scroll's context-aware `TryFromCtx`/`FromCtx` design: rather
+/// than hardcoding `from_le_bytes` everywhere, every `InternalDT` node carries
+/// the `Endian` it was parsed with (an `NdArray` takes it from its element
+/// type), so the exact same tree can be reused
+/// against either byte order (MIPS/PPC, some ARM configs, network gear, ...).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+impl Endian {
+    /// The endianness of the host this extension was compiled for.
+    pub fn native() -> Self {
+        if cfg!(target_endian = "big") {
+            Endian::Big
+        } else {
+            Endian::Little
+        }
+    }
+}
+
+/// Rounds `n` up to the nearest multiple of `align`.
+fn round_up(n: usize, align: usize) -> usize {
+    if align <= 1 {
+        n
+    } else {
+        (n + align - 1) / align * align
+    }
+}
+
+/// A `(name, type, bits)` `_fields_` entry: `bit_offset`/`bit_width` describe
+/// where the value lives within its storage unit (`StructField::dt`, `StructField::offset`),
+/// matching consecutive C bitfields of the same storage size packed into one storage unit.
+#[derive(Clone, Debug)]
+pub struct BitField {
+    pub bit_offset: u8,
+    pub bit_width: u8,
+}
+
+/// A single resolved `Structure`/`Union` field: its byte offset, its type,
+/// and — for bitfields — where within that type's storage unit it lives.
+#[derive(Clone, Debug)]
+pub struct StructField {
+    pub offset: usize,
+    pub dt: InternalDT,
+    pub bitfield: Option<BitField>,
+}
+
 /// Please stick to explicit widths, no c_int nonsense!
 #[derive(Clone, Debug)]
 pub enum InternalDT {
     /// Represents the C signed char datatype, and interprets the value as small integer.
-    Byte,
+    Byte(Endian),
     /// Represents the C unsigned char datatype, it interprets the value as small integer.
-    UByte,
+    UByte(Endian),
     /// Represents the C char datatype, and interprets the value as a single character.
-    Char,
+    Char(Endian),
     /// Represents the C wchar_t datatype, and interprets the value as a single character unicode string.
-    WideChar,
+    WideChar(Endian),
     /// Represents the C double datatype.
-    Double,
+    Double(Endian),
     /// Represents the C long double datatype. On platforms where sizeof(long double) == sizeof(double) it is an alias to c_double.
     /// For more info see: https://github.com/rust-lang/rust-bindgen/issues/1549
-    LongDouble,
+    /// The trailing `usize` is the configured width (`_longdouble_size_`, default 16).
+    LongDouble(Endian, usize),
     /// Represents the C float datatype.
-    Float,
+    Float(Endian),
     /// Represents the C signed short datatype. no overflow checking is done.
-    Short,
+    Short(Endian),
     /// Represents the C unsigned short datatype. no overflow checking is done.
-    UShort,
+    UShort(Endian),
     /// Represents the C signed int datatype. no overflow checking is done. On platforms where sizeof(int) == sizeof(long) it is an alias to c_long.
-    Int,
+    Int(Endian),
     /// Represents the C unsigned int datatype. no overflow checking is done. On platforms where sizeof(int) == sizeof(long) it is an alias for c_ulong.
-    UInt,
+    UInt(Endian),
     /// Represents the C signed long datatype.
-    Long,
+    Long(Endian),
     /// Represents the C unsigned long datatype.
-    ULong,
+    ULong(Endian),
     /// Represents the C signed long long datatype.
-    LongLong,
+    LongLong(Endian),
     /// Represents the C unsigned long long datatype.
-    ULongLong,
+    ULongLong(Endian),
     /// Native pointer type, backed by `MF_Pointer`.
-    Pointer(PyObject, u32),
+    Pointer(PyObject, u32, Endian),
     // Backed by the ctypes (ctype * size) syntax.
-    Array(PyObject, Box<InternalDT>, u32),
-    /// Any python class with a ctypes _fields_ attribute.
-    Structure(PyObject, IndexMap<String, (usize, InternalDT)>),
+    Array(PyObject, Box<InternalDT>, u32, Endian),
+    /// Strided N-dimensional array, materialized as a `numpy.ndarray` instead
+    /// of a ctypes array. Carries the element type, the `shape` (one entry
+    /// per dimension) and the byte `strides` used to walk the source buffer,
+    /// which default to the contiguous row-major layout but may describe a
+    /// padded, transposed, or otherwise non-contiguous view.
+    NdArray(PyObject, Box<InternalDT>, Vec<u32>, Vec<usize>),
+    /// Any python class with a ctypes _fields_ attribute. `Union` classes are
+    /// represented the same way, with every field placed at offset 0. The
+    /// trailing `usize` is the struct's own alignment (the max of its
+    /// fields', capped by an optional `_pack_`), used to pad `size()` up to
+    /// a multiple of it, matching the ctypes/C ABI.
+    Structure(PyObject, IndexMap<String, StructField>, Endian, usize),
 }
 
 impl InternalDT {
     pub fn py_from_bytes(&self, bytes: Vec<u8>) -> crate::Result<PyObject> {
         Python::with_gil(|py| match self {
-            InternalDT::Byte => Ok(i8::from_le_bytes(bytes[..].try_into()?).to_object(py)),
-            InternalDT::UByte => Ok(u8::from_le_bytes(bytes[..].try_into()?).to_object(py)),
-            InternalDT::Char => Ok(c_char::from_le_bytes(bytes[..].try_into()?).to_object(py)),
-            InternalDT::WideChar => Ok(u16::from_le_bytes(bytes[..].try_into()?).to_object(py)),
-            InternalDT::Double => Ok(c_double::from_le_bytes(bytes[..].try_into()?).to_object(py)),
-            InternalDT::LongDouble => todo!(),
-            InternalDT::Float => Ok(c_float::from_le_bytes(bytes[..].try_into()?).to_object(py)),
-            InternalDT::Short => Ok(c_short::from_le_bytes(bytes[..].try_into()?).to_object(py)),
-            InternalDT::UShort => Ok(c_ushort::from_le_bytes(bytes[..].try_into()?).to_object(py)),
-            InternalDT::Int => Ok(c_int::from_le_bytes(bytes[..].try_into()?).to_object(py)),
-            InternalDT::UInt => Ok(c_uint::from_le_bytes(bytes[..].try_into()?).to_object(py)),
-            InternalDT::Long => Ok(c_long::from_le_bytes(bytes[..].try_into()?).to_object(py)),
-            InternalDT::ULong => Ok(c_ulong::from_le_bytes(bytes[..].try_into()?).to_object(py)),
-            InternalDT::LongLong => {
+            InternalDT::Byte(Endian::Little) => {
+                Ok(i8::from_le_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::Byte(Endian::Big) => {
+                Ok(i8::from_be_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::UByte(Endian::Little) => {
+                Ok(u8::from_le_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::UByte(Endian::Big) => {
+                Ok(u8::from_be_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::Char(Endian::Little) => {
+                Ok(c_char::from_le_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::Char(Endian::Big) => {
+                Ok(c_char::from_be_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::WideChar(Endian::Little) => {
+                Ok(u16::from_le_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::WideChar(Endian::Big) => {
+                Ok(u16::from_be_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::Double(Endian::Little) => {
+                Ok(c_double::from_le_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::Double(Endian::Big) => {
+                Ok(c_double::from_be_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::LongDouble(endian, _) => {
+                let payload = Self::longdouble_payload(&bytes, *endian)?;
+                Ok(Self::longdouble_decode(payload).to_object(py))
+            }
+            InternalDT::Float(Endian::Little) => {
+                Ok(c_float::from_le_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::Float(Endian::Big) => {
+                Ok(c_float::from_be_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::Short(Endian::Little) => {
+                Ok(c_short::from_le_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::Short(Endian::Big) => {
+                Ok(c_short::from_be_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::UShort(Endian::Little) => {
+                Ok(c_ushort::from_le_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::UShort(Endian::Big) => {
+                Ok(c_ushort::from_be_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::Int(Endian::Little) => {
+                Ok(c_int::from_le_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::Int(Endian::Big) => {
+                Ok(c_int::from_be_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::UInt(Endian::Little) => {
+                Ok(c_uint::from_le_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::UInt(Endian::Big) => {
+                Ok(c_uint::from_be_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::Long(Endian::Little) => {
+                Ok(c_long::from_le_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::Long(Endian::Big) => {
+                Ok(c_long::from_be_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::ULong(Endian::Little) => {
+                Ok(c_ulong::from_le_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::ULong(Endian::Big) => {
+                Ok(c_ulong::from_be_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::LongLong(Endian::Little) => {
                 Ok(c_longlong::from_le_bytes(bytes[..].try_into()?).to_object(py))
             }
-            InternalDT::ULongLong => {
+            InternalDT::LongLong(Endian::Big) => {
+                Ok(c_longlong::from_be_bytes(bytes[..].try_into()?).to_object(py))
+            }
+            InternalDT::ULongLong(Endian::Little) => {
                 Ok(c_ulonglong::from_le_bytes(bytes[..].try_into()?).to_object(py))
             }
-            InternalDT::Pointer(class, _) => {
-                Ok(class.call1(py, (umem::from_le_bytes(bytes[..self.size()].try_into()?),))?)
+            InternalDT::ULongLong(Endian::Big) => {
+                Ok(c_ulonglong::from_be_bytes(bytes[..].try_into()?).to_object(py))
             }
-            InternalDT::Array(class, dt, _) => Ok(class.call1(
+            InternalDT::Pointer(class, _, Endian::Little) => Ok(class.call1(
                 py,
-                PyTuple::new(
-                    py,
-                    bytes
-                        .chunks(dt.size())
-                        .into_iter()
-                        .map(|w| dt.py_from_bytes(w.to_vec()).unwrap()),
-                ),
+                (umem::from_le_bytes(bytes[..self.size()].try_into()?),),
             )?),
-            InternalDT::Structure(class, dts) => {
+            InternalDT::Pointer(class, _, Endian::Big) => Ok(class.call1(
+                py,
+                (umem::from_be_bytes(bytes[..self.size()].try_into()?),),
+            )?),
+            InternalDT::Array(class, dt, _, _) => {
+                let items = bytes
+                    .chunks(dt.size())
+                    .enumerate()
+                    .map(|(i, w)| {
+                        dt.py_from_bytes(w.to_vec())
+                            .map_err(|e| Self::with_field(e, format!("[{}]", i)))
+                    })
+                    .collect::<crate::Result<Vec<_>>>()?;
+                Ok(class.call1(py, PyTuple::new(py, items))?)
+            }
+            InternalDT::NdArray(_, dt, shape, strides) => {
+                let elem_size = dt.size();
+                let flat = Self::read_strided(&bytes, shape, strides, elem_size);
+                let dtype = dt.numpy_dtype()?;
+
+                let numpy = PyModule::import(py, "numpy")?;
+                let kwargs = PyDict::new(py);
+                kwargs.set_item("dtype", dtype)?;
+                let array = numpy.call_method("frombuffer", (PyBytes::new(py, &flat),), Some(kwargs))?;
+                let shape_tuple = PyTuple::new(py, shape.iter().map(|&n| n as usize));
+                Ok(array.call_method1("reshape", (shape_tuple,))?.to_object(py))
+            }
+            InternalDT::Structure(class, dts, _, _) => {
                 let dict = PyDict::new(py);
                 dts.into_iter()
-                    .try_for_each::<_, crate::Result<()>>(|(name, (offset, dt))| {
-                        let start = *offset;
-                        let size = dt.size();
-                        let val = dt.py_from_bytes(bytes[start..(start + size)].to_vec())?;
+                    .try_for_each::<_, crate::Result<()>>(|(name, field)| {
+                        let start = field.offset;
+                        let size = field.dt.size();
+                        let raw = &bytes[start..(start + size)];
+                        let val = if let Some(bf) = &field.bitfield {
+                            let unit = Self::bits_to_u64(raw, field.dt.endian());
+                            let mask = Self::bit_mask(bf.bit_width);
+                            ((unit >> bf.bit_offset) & mask).to_object(py)
+                        } else {
+                            field
+                                .dt
+                                .py_from_bytes(raw.to_vec())
+                                .map_err(|e| Self::with_field(e, name.clone()))?
+                        };
                         dict.set_item(name.as_str(), val)?;
                         Ok(())
                     })?;
@@ -104,47 +268,145 @@ impl InternalDT {
 
     pub fn py_to_bytes(&self, obj: PyObject) -> crate::Result<Vec<u8>> {
         Python::with_gil(|py| match self {
-            InternalDT::Byte => Ok(obj.extract::<i8>(py)?.to_le_bytes().to_vec()),
-            InternalDT::UByte => Ok(obj.extract::<u8>(py)?.to_le_bytes().to_vec()),
-            InternalDT::Char => Ok(obj.extract::<c_char>(py)?.to_le_bytes().to_vec()),
+            InternalDT::Byte(Endian::Little) => Ok(obj.extract::<i8>(py)?.to_le_bytes().to_vec()),
+            InternalDT::Byte(Endian::Big) => Ok(obj.extract::<i8>(py)?.to_be_bytes().to_vec()),
+            InternalDT::UByte(Endian::Little) => Ok(obj.extract::<u8>(py)?.to_le_bytes().to_vec()),
+            InternalDT::UByte(Endian::Big) => Ok(obj.extract::<u8>(py)?.to_be_bytes().to_vec()),
+            InternalDT::Char(Endian::Little) => {
+                Ok(obj.extract::<c_char>(py)?.to_le_bytes().to_vec())
+            }
+            InternalDT::Char(Endian::Big) => {
+                Ok(obj.extract::<c_char>(py)?.to_be_bytes().to_vec())
+            }
             // OS widechar encoding.
-            InternalDT::WideChar => Ok(obj.extract::<u16>(py)?.to_le_bytes().to_vec()),
-            InternalDT::Double => Ok(obj.extract::<c_double>(py)?.to_le_bytes().to_vec()),
-            InternalDT::LongDouble => todo!(),
-            InternalDT::Float => Ok(obj.extract::<c_float>(py)?.to_le_bytes().to_vec()),
-            InternalDT::Short => Ok(obj.extract::<c_short>(py)?.to_le_bytes().to_vec()),
-            InternalDT::UShort => Ok(obj.extract::<c_ushort>(py)?.to_le_bytes().to_vec()),
-            InternalDT::Int => Ok(obj.extract::<c_int>(py)?.to_le_bytes().to_vec()),
-            InternalDT::UInt => Ok(obj.extract::<c_uint>(py)?.to_le_bytes().to_vec()),
-            InternalDT::Long => Ok(obj.extract::<c_long>(py)?.to_le_bytes().to_vec()),
-            InternalDT::ULong => Ok(obj.extract::<c_ulong>(py)?.to_le_bytes().to_vec()),
-            InternalDT::LongLong => Ok(obj.extract::<c_longlong>(py)?.to_le_bytes().to_vec()),
-            InternalDT::ULongLong => Ok(obj.extract::<c_ulonglong>(py)?.to_le_bytes().to_vec()),
-            InternalDT::Pointer(_, _) => Ok(obj
+            InternalDT::WideChar(Endian::Little) => {
+                Ok(obj.extract::<u16>(py)?.to_le_bytes().to_vec())
+            }
+            InternalDT::WideChar(Endian::Big) => {
+                Ok(obj.extract::<u16>(py)?.to_be_bytes().to_vec())
+            }
+            InternalDT::Double(Endian::Little) => {
+                Ok(obj.extract::<c_double>(py)?.to_le_bytes().to_vec())
+            }
+            InternalDT::Double(Endian::Big) => {
+                Ok(obj.extract::<c_double>(py)?.to_be_bytes().to_vec())
+            }
+            InternalDT::LongDouble(endian, width) => {
+                let payload = Self::longdouble_encode(obj.extract::<c_double>(py)?);
+                Self::longdouble_bytes(payload, *width, *endian)
+            }
+            InternalDT::Float(Endian::Little) => {
+                Ok(obj.extract::<c_float>(py)?.to_le_bytes().to_vec())
+            }
+            InternalDT::Float(Endian::Big) => {
+                Ok(obj.extract::<c_float>(py)?.to_be_bytes().to_vec())
+            }
+            InternalDT::Short(Endian::Little) => {
+                Ok(obj.extract::<c_short>(py)?.to_le_bytes().to_vec())
+            }
+            InternalDT::Short(Endian::Big) => {
+                Ok(obj.extract::<c_short>(py)?.to_be_bytes().to_vec())
+            }
+            InternalDT::UShort(Endian::Little) => {
+                Ok(obj.extract::<c_ushort>(py)?.to_le_bytes().to_vec())
+            }
+            InternalDT::UShort(Endian::Big) => {
+                Ok(obj.extract::<c_ushort>(py)?.to_be_bytes().to_vec())
+            }
+            InternalDT::Int(Endian::Little) => Ok(obj.extract::<c_int>(py)?.to_le_bytes().to_vec()),
+            InternalDT::Int(Endian::Big) => Ok(obj.extract::<c_int>(py)?.to_be_bytes().to_vec()),
+            InternalDT::UInt(Endian::Little) => {
+                Ok(obj.extract::<c_uint>(py)?.to_le_bytes().to_vec())
+            }
+            InternalDT::UInt(Endian::Big) => Ok(obj.extract::<c_uint>(py)?.to_be_bytes().to_vec()),
+            InternalDT::Long(Endian::Little) => {
+                Ok(obj.extract::<c_long>(py)?.to_le_bytes().to_vec())
+            }
+            InternalDT::Long(Endian::Big) => Ok(obj.extract::<c_long>(py)?.to_be_bytes().to_vec()),
+            InternalDT::ULong(Endian::Little) => {
+                Ok(obj.extract::<c_ulong>(py)?.to_le_bytes().to_vec())
+            }
+            InternalDT::ULong(Endian::Big) => {
+                Ok(obj.extract::<c_ulong>(py)?.to_be_bytes().to_vec())
+            }
+            InternalDT::LongLong(Endian::Little) => {
+                Ok(obj.extract::<c_longlong>(py)?.to_le_bytes().to_vec())
+            }
+            InternalDT::LongLong(Endian::Big) => {
+                Ok(obj.extract::<c_longlong>(py)?.to_be_bytes().to_vec())
+            }
+            InternalDT::ULongLong(Endian::Little) => {
+                Ok(obj.extract::<c_ulonglong>(py)?.to_le_bytes().to_vec())
+            }
+            InternalDT::ULongLong(Endian::Big) => {
+                Ok(obj.extract::<c_ulonglong>(py)?.to_be_bytes().to_vec())
+            }
+            InternalDT::Pointer(_, _, Endian::Little) => Ok(obj
                 .getattr(py, "addr")?
                 .extract::<umem>(py)?
                 .to_le_bytes()[..self.size()]
                 .to_vec()),
-            InternalDT::Array(_, dt, len) => {
+            InternalDT::Pointer(_, _, Endian::Big) => Ok(obj
+                .getattr(py, "addr")?
+                .extract::<umem>(py)?
+                .to_be_bytes()[(size_of::<umem>() - self.size())..]
+                .to_vec()),
+            InternalDT::Array(_, dt, len, _) => {
                 let mut bytes = Vec::new();
                 for i in 0..*len {
                     let item_obj = obj.call_method1(py, "__getitem__", (i,))?;
-                    bytes.append(&mut dt.py_to_bytes(item_obj)?);
+                    let mut item_bytes = dt
+                        .py_to_bytes(item_obj)
+                        .map_err(|e| Self::with_field(e, format!("[{}]", i)))?;
+                    bytes.append(&mut item_bytes);
+                }
+                Ok(bytes)
+            }
+            InternalDT::NdArray(_, dt, shape, strides) => {
+                let elem_size = dt.size();
+                let dtype = dt.numpy_dtype()?;
+                let coerced = obj.call_method1(py, "astype", (dtype,))?;
+                let flat: Vec<u8> = coerced.call_method0(py, "tobytes")?.extract(py)?;
+
+                let expected_len = shape.iter().product::<u32>() as usize * elem_size;
+                if flat.len() != expected_len {
+                    return Err(MemflowPyError::InvalidType(format!(
+                        "expected {} bytes for NdArray shape {:?}, got {}",
+                        expected_len, shape, flat.len()
+                    )));
                 }
+
+                let mut bytes = vec![0u8; self.size()];
+                Self::write_strided(&mut bytes, &flat, shape, strides, elem_size);
                 Ok(bytes)
             }
             // NOTE: The passed object is not checked to be type of structure.
-            InternalDT::Structure(_, dts) => {
+            InternalDT::Structure(_, dts, _, _) => {
                 let mut bytes = Vec::new();
                 bytes.resize(self.size(), 0);
                 dts.into_iter()
-                    .try_for_each::<_, crate::Result<()>>(|(name, (offset, dt))| {
-                        if let Ok(val_obj) = obj.getattr(py, name.as_str()) {
-                            bytes.splice(offset..&(offset + dt.size()), dt.py_to_bytes(val_obj)?);
-                            Ok(())
+                    .try_for_each::<_, crate::Result<()>>(|(name, field)| {
+                        let val_obj = obj.getattr(py, name.as_str()).map_err(|_| {
+                            Self::with_field(MemflowPyError::MissingAttribute(name.clone()), name.clone())
+                        })?;
+                        let start = field.offset;
+                        let size = field.dt.size();
+                        if let Some(bf) = &field.bitfield {
+                            let endian = field.dt.endian();
+                            let mask = Self::bit_mask(bf.bit_width);
+                            let value: u64 = val_obj.extract(py)?;
+                            let shifted = (value & mask) << bf.bit_offset;
+                            let current = Self::bits_to_u64(&bytes[start..(start + size)], endian);
+                            let unit = Self::u64_to_bits(current | shifted, size, endian);
+                            bytes[start..(start + size)].copy_from_slice(&unit);
                         } else {
-                            Err(MemflowPyError::MissingAttribute(name.to_owned()))
+                            let field_bytes = field
+                                .dt
+                                .py_to_bytes(val_obj)
+                                .map_err(|e| Self::with_field(e, name.clone()))?;
+                            bytes.splice(start..&(start + size), field_bytes);
                         }
+                        Ok(())
                     })?;
                 Ok(bytes)
             }
@@ -153,30 +415,342 @@ impl InternalDT {
 
     pub fn size(&self) -> usize {
         match self {
-            InternalDT::Byte => size_of::<c_schar>(),
-            InternalDT::UByte => size_of::<c_uchar>(),
-            InternalDT::Char => size_of::<c_char>(),
-            InternalDT::WideChar => size_of::<c_char>() * 2,
-            InternalDT::Short => size_of::<c_short>(),
-            InternalDT::UShort => size_of::<c_ushort>(),
-            InternalDT::Double => size_of::<c_double>(),
-            InternalDT::LongDouble => size_of::<c_double>() * 2,
-            InternalDT::Float => size_of::<c_float>(),
-            InternalDT::Int => size_of::<c_int>(),
-            InternalDT::UInt => size_of::<c_uint>(),
-            InternalDT::Long => size_of::<c_long>(),
-            InternalDT::ULong => size_of::<c_ulong>(),
-            InternalDT::LongLong => size_of::<c_longlong>(),
-            InternalDT::ULongLong => size_of::<c_ulonglong>(),
-            InternalDT::Pointer(_, byteness) => *byteness as usize,
-            InternalDT::Array(_, dt, len) => dt.size() * (*len as usize),
-            InternalDT::Structure(_, dts) => {
-                let (_, max_dt) = dts
+            InternalDT::Byte(_) => size_of::<c_schar>(),
+            InternalDT::UByte(_) => size_of::<c_uchar>(),
+            InternalDT::Char(_) => size_of::<c_char>(),
+            InternalDT::WideChar(_) => size_of::<c_char>() * 2,
+            InternalDT::Short(_) => size_of::<c_short>(),
+            InternalDT::UShort(_) => size_of::<c_ushort>(),
+            InternalDT::Double(_) => size_of::<c_double>(),
+            InternalDT::LongDouble(_, width) => *width,
+            InternalDT::Float(_) => size_of::<c_float>(),
+            InternalDT::Int(_) => size_of::<c_int>(),
+            InternalDT::UInt(_) => size_of::<c_uint>(),
+            InternalDT::Long(_) => size_of::<c_long>(),
+            InternalDT::ULong(_) => size_of::<c_ulong>(),
+            InternalDT::LongLong(_) => size_of::<c_longlong>(),
+            InternalDT::ULongLong(_) => size_of::<c_ulonglong>(),
+            InternalDT::Pointer(_, byteness, _) => *byteness as usize,
+            InternalDT::Array(_, dt, len, _) => dt.size() * (*len as usize),
+            InternalDT::NdArray(_, dt, shape, strides) => {
+                // Minimal span touched by the strided walk, not just the
+                // contiguous product: a transposed or overlapping view can
+                // reach further (or less far) than `shape.product() * size`.
+                shape
                     .iter()
-                    .max_by(|(_, x), (_, y)| (x.0 + x.1.size()).cmp(&(y.0 + y.1.size())))
+                    .zip(strides.iter())
+                    .map(|(&n, &stride)| (n as usize).saturating_sub(1) * stride)
+                    .sum::<usize>()
+                    + dt.size()
+            }
+            InternalDT::Structure(_, dts, _, align) => {
+                let (_, max_field) = dts
+                    .iter()
+                    .max_by(|(_, x), (_, y)| {
+                        (x.offset + x.dt.size()).cmp(&(y.offset + y.dt.size()))
+                    })
                     .unwrap();
-                // Offset + dt size
-                max_dt.0 + max_dt.1.size()
+                // Offset + dt size, padded out to the struct's own alignment.
+                round_up(max_field.offset + max_field.dt.size(), *align)
+            }
+        }
+    }
+
+    /// The ctypes-compatible alignment of this type: natural alignment for
+    /// scalars, pointer width for pointers, the element's alignment for
+    /// arrays, and the (already `_pack_`-capped) stored alignment for
+    /// structures/unions.
+    pub fn alignment(&self) -> usize {
+        match self {
+            InternalDT::Byte(_) => std::mem::align_of::<c_schar>(),
+            InternalDT::UByte(_) => std::mem::align_of::<c_uchar>(),
+            InternalDT::Char(_) => std::mem::align_of::<c_char>(),
+            InternalDT::WideChar(_) => std::mem::align_of::<u16>(),
+            InternalDT::Short(_) => std::mem::align_of::<c_short>(),
+            InternalDT::UShort(_) => std::mem::align_of::<c_ushort>(),
+            InternalDT::Double(_) => std::mem::align_of::<c_double>(),
+            InternalDT::LongDouble(_, _) => std::mem::align_of::<c_double>(),
+            InternalDT::Float(_) => std::mem::align_of::<c_float>(),
+            InternalDT::Int(_) => std::mem::align_of::<c_int>(),
+            InternalDT::UInt(_) => std::mem::align_of::<c_uint>(),
+            InternalDT::Long(_) => std::mem::align_of::<c_long>(),
+            InternalDT::ULong(_) => std::mem::align_of::<c_ulong>(),
+            InternalDT::LongLong(_) => std::mem::align_of::<c_longlong>(),
+            InternalDT::ULongLong(_) => std::mem::align_of::<c_ulonglong>(),
+            InternalDT::Pointer(_, byteness, _) => *byteness as usize,
+            InternalDT::Array(_, dt, _, _) => dt.alignment(),
+            InternalDT::NdArray(_, dt, _, _) => dt.alignment(),
+            InternalDT::Structure(_, _, _, align) => *align,
+        }
+    }
+
+    /// The `Endian` this node (or, for an `NdArray`, its element type) was
+    /// parsed with.
+    fn endian(&self) -> Endian {
+        match self {
+            InternalDT::Byte(e)
+            | InternalDT::UByte(e)
+            | InternalDT::Char(e)
+            | InternalDT::WideChar(e)
+            | InternalDT::Double(e)
+            | InternalDT::Float(e)
+            | InternalDT::Short(e)
+            | InternalDT::UShort(e)
+            | InternalDT::Int(e)
+            | InternalDT::UInt(e)
+            | InternalDT::Long(e)
+            | InternalDT::ULong(e)
+            | InternalDT::LongLong(e)
+            | InternalDT::ULongLong(e)
+            | InternalDT::Pointer(_, _, e)
+            | InternalDT::Array(_, _, _, e)
+            | InternalDT::Structure(_, _, e, _) => *e,
+            InternalDT::LongDouble(e, _) => *e,
+            InternalDT::NdArray(_, dt, _, _) => dt.endian(),
+        }
+    }
+
+    /// Wraps `err` with the field/index `segment` it occurred under, building
+    /// up a dotted path (e.g. `Player.inventory[2].count`).
+    fn with_field(err: MemflowPyError, segment: impl Into<String>) -> MemflowPyError {
+        let segment = segment.into();
+        match err {
+            MemflowPyError::FieldPath(path, source) => {
+                MemflowPyError::FieldPath(Self::join_path(&segment, &path), source)
+            }
+            other => MemflowPyError::FieldPath(segment, Box::new(other)),
+        }
+    }
+
+    /// Joins an outer path `segment` onto an already-accumulated inner `path`.
+    fn join_path(segment: &str, path: &str) -> String {
+        if path.starts_with('[') {
+            format!("{}{}", segment, path)
+        } else {
+            format!("{}.{}", segment, path)
+        }
+    }
+
+    /// A mask selecting the low `bit_width` bits (saturating at 64).
+    fn bit_mask(bit_width: u8) -> u64 {
+        if bit_width >= 64 {
+            u64::MAX
+        } else {
+            (1_u64 << bit_width) - 1
+        }
+    }
+
+    /// Reads a bitfield's storage unit as a `u64`, honoring `endian`.
+    fn bits_to_u64(bytes: &[u8], endian: Endian) -> u64 {
+        let mut buf = [0_u8; 8];
+        match endian {
+            Endian::Little => buf[..bytes.len()].copy_from_slice(bytes),
+            Endian::Big => buf[(8 - bytes.len())..].copy_from_slice(bytes),
+        }
+        match endian {
+            Endian::Little => u64::from_le_bytes(buf),
+            Endian::Big => u64::from_be_bytes(buf),
+        }
+    }
+
+    /// Inverse of [`Self::bits_to_u64`].
+    fn u64_to_bits(value: u64, size: usize, endian: Endian) -> Vec<u8> {
+        match endian {
+            Endian::Little => value.to_le_bytes()[..size].to_vec(),
+            Endian::Big => value.to_be_bytes()[(8 - size)..].to_vec(),
+        }
+    }
+
+    /// Extracts the 10-byte x87 extended-precision payload from a `LongDouble` storage unit.
+    fn longdouble_payload(bytes: &[u8], endian: Endian) -> crate::Result<[u8; 10]> {
+        if bytes.len() < 10 {
+            return Err(MemflowPyError::InvalidType(format!(
+                "LongDouble storage unit must be at least 10 bytes wide, got {}",
+                bytes.len()
+            )));
+        }
+        let mut payload = [0_u8; 10];
+        match endian {
+            Endian::Little => payload.copy_from_slice(&bytes[..10]),
+            Endian::Big => {
+                let tail = &bytes[(bytes.len() - 10)..];
+                for (dst, src) in payload.iter_mut().zip(tail.iter().rev()) {
+                    *dst = *src;
+                }
+            }
+        }
+        Ok(payload)
+    }
+
+    /// Inverse of [`Self::longdouble_payload`].
+    fn longdouble_bytes(payload: [u8; 10], width: usize, endian: Endian) -> crate::Result<Vec<u8>> {
+        if width < 10 {
+            return Err(MemflowPyError::InvalidType(format!(
+                "_longdouble_size_ must be at least 10 (the x87 payload width), got {}",
+                width
+            )));
+        }
+        let mut out = vec![0_u8; width];
+        match endian {
+            Endian::Little => out[..10].copy_from_slice(&payload),
+            Endian::Big => {
+                let tail_start = width - 10;
+                for (dst, src) in out[tail_start..].iter_mut().zip(payload.iter().rev()) {
+                    *dst = *src;
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Multiplies `value` by `2^exp` in bounded steps so intermediate `powi` calls never under/overflow.
+    fn scale_pow2(value: f64, exp: i32) -> f64 {
+        const STEP: i32 = 1000;
+        let mut result = value;
+        let mut remaining = exp;
+        while remaining > STEP {
+            result *= 2f64.powi(STEP);
+            remaining -= STEP;
+        }
+        while remaining < -STEP {
+            result *= 2f64.powi(-STEP);
+            remaining += STEP;
+        }
+        result * 2f64.powi(remaining)
+    }
+
+    /// Decodes an x87 80-bit extended-precision payload to the nearest `f64`.
+    fn longdouble_decode(payload: [u8; 10]) -> f64 {
+        let mantissa = u64::from_le_bytes(payload[0..8].try_into().unwrap());
+        let sign_exponent = u16::from_le_bytes([payload[8], payload[9]]);
+        let sign = sign_exponent & 0x8000 != 0;
+        let exponent = sign_exponent & 0x7FFF;
+
+        let magnitude = if exponent == 0x7FFF {
+            // Infinity has the explicit integer bit set and a zero fraction;
+            // anything else with a maxed-out exponent is a NaN.
+            if mantissa == 1_u64 << 63 {
+                f64::INFINITY
+            } else {
+                f64::NAN
+            }
+        } else if exponent == 0 && mantissa == 0 {
+            0.0
+        } else {
+            // Denormals (exponent == 0) use the fixed unbiased exponent
+            // -16382 rather than -bias, with no implicit integer bit
+            // assumed beyond whatever the mantissa already encodes.
+            let unbiased = if exponent == 0 {
+                -16382
+            } else {
+                exponent as i32 - 16383
+            };
+            Self::scale_pow2(mantissa as f64, unbiased - 63)
+        };
+
+        if sign {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    /// Inverse of [`Self::longdouble_decode`].
+    fn longdouble_encode(value: f64) -> [u8; 10] {
+        let sign = value.is_sign_negative();
+        let abs = value.abs();
+
+        let (mantissa, exponent): (u64, u16) = if value.is_nan() {
+            (1_u64 << 63 | 1_u64 << 62, 0x7FFF)
+        } else if abs.is_infinite() {
+            (1_u64 << 63, 0x7FFF)
+        } else if abs == 0.0 {
+            (0, 0)
+        } else {
+            let bits = abs.to_bits();
+            let biased_exp = (bits >> 52) & 0x7FF;
+            let frac = bits & 0xF_FFFF_FFFF_FFFF;
+            if biased_exp == 0 {
+                let scaled = (abs * 2f64.powi(84)).to_bits();
+                let biased_exp = ((scaled >> 52) & 0x7FF) as i64;
+                let frac = scaled & 0xF_FFFF_FFFF_FFFF;
+                let unbiased = biased_exp - 1023 - 84;
+                ((1_u64 << 63) | (frac << 11), (unbiased + 16383) as u16)
+            } else {
+                let unbiased = biased_exp as i64 - 1023;
+                ((1_u64 << 63) | (frac << 11), (unbiased + 16383) as u16)
+            }
+        };
+
+        let sign_exponent = exponent | if sign { 0x8000 } else { 0 };
+        let mut payload = [0_u8; 10];
+        payload[0..8].copy_from_slice(&mantissa.to_le_bytes());
+        payload[8..10].copy_from_slice(&sign_exponent.to_le_bytes());
+        payload
+    }
+
+    /// The `numpy` dtype string (e.g. `"<i4"`, `">f8"`) for a scalar element type.
+    fn numpy_dtype(&self) -> crate::Result<String> {
+        let (code, endian) = match self {
+            InternalDT::Byte(e) => ("i1", e),
+            InternalDT::UByte(e) => ("u1", e),
+            InternalDT::Char(e) => ("i1", e),
+            InternalDT::WideChar(e) => ("u2", e),
+            InternalDT::Double(e) => ("f8", e),
+            InternalDT::Float(e) => ("f4", e),
+            InternalDT::Short(e) => ("i2", e),
+            InternalDT::UShort(e) => ("u2", e),
+            InternalDT::Int(e) => ("i4", e),
+            InternalDT::UInt(e) => ("u4", e),
+            InternalDT::Long(e) if self.size() == 4 => ("i4", e),
+            InternalDT::Long(e) => ("i8", e),
+            InternalDT::ULong(e) if self.size() == 4 => ("u4", e),
+            InternalDT::ULong(e) => ("u8", e),
+            InternalDT::LongLong(e) => ("i8", e),
+            InternalDT::ULongLong(e) => ("u8", e),
+            other => {
+                return Err(MemflowPyError::InvalidType(format!(
+                    "{:?} has no numpy dtype",
+                    other
+                )))
+            }
+        };
+        Ok(format!(
+            "{}{}",
+            match endian {
+                Endian::Little => "<",
+                Endian::Big => ">",
+            },
+            code
+        ))
+    }
+
+    /// Reads `shape`/`strides` in row-major order into a fresh, contiguous buffer.
+    fn read_strided(bytes: &[u8], shape: &[u32], strides: &[usize], elem_size: usize) -> Vec<u8> {
+        let mut out = Vec::new();
+        Self::walk_strided(shape, strides, 0, &mut |offset| {
+            out.extend_from_slice(&bytes[offset..offset + elem_size]);
+        });
+        out
+    }
+
+    /// Inverse of [`Self::read_strided`].
+    fn write_strided(bytes: &mut [u8], flat: &[u8], shape: &[u32], strides: &[usize], elem_size: usize) {
+        let mut cursor = 0_usize;
+        Self::walk_strided(shape, strides, 0, &mut |offset| {
+            bytes[offset..offset + elem_size].copy_from_slice(&flat[cursor..cursor + elem_size]);
+            cursor += elem_size;
+        });
+    }
+
+    fn walk_strided(shape: &[u32], strides: &[usize], base: usize, visit: &mut impl FnMut(usize)) {
+        match shape.split_first() {
+            None => visit(base),
+            Some((&dim, rest_shape)) => {
+                let (&stride, rest_strides) = strides.split_first().unwrap();
+                for i in 0..dim {
+                    Self::walk_strided(rest_shape, rest_strides, base + i as usize * stride, visit);
+                }
             }
         }
     }
@@ -186,11 +760,41 @@ impl TryFrom<PyObject> for InternalDT {
     type Error = MemflowPyError;
 
     fn try_from(value: PyObject) -> Result<Self, Self::Error> {
+        Self::from_pyobject_ctx(value, None)
+    }
+}
+
+impl InternalDT {
+    /// Parses a ctypes-alike Python class into an `InternalDT`, honoring an
+    /// optional `_endian_` class attribute (`"little"`/`"big"`, matching
+    /// scroll's `Endian`). `parent_endian` is the endianness inherited from
+    /// the enclosing `Array`/`NdArray`/`Structure`/`Union`, if any; a class without
+    /// its own `_endian_` falls back to it, or to the native endianness at
+    /// the root of the tree.
+    fn from_pyobject_ctx(
+        value: PyObject,
+        parent_endian: Option<Endian>,
+    ) -> Result<Self, MemflowPyError> {
         let base_name: String = Python::with_gil(|py| {
             let base_obj: PyObject = value.getattr(py, "__base__")?.extract(py)?;
             base_obj.getattr(py, "__name__")?.extract(py)
         })?;
 
+        let endian = Python::with_gil(|py| match value.getattr(py, "_endian_") {
+            Ok(attr) => {
+                let ident: String = attr.extract(py)?;
+                match ident.as_str() {
+                    "little" => Ok(Endian::Little),
+                    "big" => Ok(Endian::Big),
+                    other => Err(MemflowPyError::InvalidType(format!(
+                        "unknown _endian_ `{}`",
+                        other
+                    ))),
+                }
+            }
+            Err(_) => Ok(parent_endian.unwrap_or_else(Endian::native)),
+        })?;
+
         // NOTE: While we do try to follow ctypes there is no guarantee that it will work.
         match base_name.as_str() {
             "CDataType" | "_SimpleCData" => {
@@ -198,25 +802,35 @@ impl TryFrom<PyObject> for InternalDT {
                 let type_ident: String =
                     Python::with_gil(|py| value.getattr(py, "_type_")?.extract(py))?;
                 let dt = match type_ident.as_str() {
-                    "b" => Self::Byte,
-                    "B" | "?" => Self::UByte,
-                    "c" => Self::Char,
-                    "u" => Self::WideChar,
+                    "b" => Self::Byte(endian),
+                    "B" | "?" => Self::UByte(endian),
+                    "c" => Self::Char(endian),
+                    "u" => Self::WideChar(endian),
                     "z" | "Z" => {
-                        unimplemented!("please use `read_char_string` and `read_wchar_string`")
+                        return Err(MemflowPyError::Unimplemented(
+                            "please use `read_char_string` and `read_wchar_string`",
+                        ))
+                    }
+                    "d" => Self::Double(endian),
+                    "g" => {
+                        let width: usize = Python::with_gil(|py| {
+                            match value.getattr(py, "_longdouble_size_") {
+                                Ok(val) => val.extract(py),
+                                Err(_) => Ok(16),
+                            }
+                        })?;
+                        Self::LongDouble(endian, width)
                     }
-                    "d" => Self::Double,
-                    "g" => Self::LongDouble,
-                    "f" => Self::Float,
-                    "h" => Self::Short,
-                    "H" => Self::UShort,
-                    "i" => Self::Int,
-                    "I" => Self::UInt,
-                    "l" => Self::Long,
-                    "L" => Self::ULong,
-                    "q" => Self::LongLong,
-                    "Q" => Self::ULongLong,
-                    name => unreachable!("unknown type identifier `{}`", name),
+                    "f" => Self::Float(endian),
+                    "h" => Self::Short(endian),
+                    "H" => Self::UShort(endian),
+                    "i" => Self::Int(endian),
+                    "I" => Self::UInt(endian),
+                    "l" => Self::Long(endian),
+                    "L" => Self::ULong(endian),
+                    "q" => Self::LongLong(endian),
+                    "Q" => Self::ULongLong(endian),
+                    name => return Err(MemflowPyError::UnknownTypeIdent(name.to_owned())),
                 };
                 Ok(dt)
             }
@@ -226,7 +840,7 @@ impl TryFrom<PyObject> for InternalDT {
                     // If we are passed a pointer with no set byteness we assume the pointer to be local system width.
                     Err(_) => Ok(size_of::<usize>() as u32),
                 })?;
-                Ok(Self::Pointer(value, byteness))
+                Ok(Self::Pointer(value, byteness, endian))
             }
             "Array" => {
                 let (len, ty_obj) = Python::with_gil::<_, crate::Result<(u32, PyObject)>>(|py| {
@@ -235,65 +849,482 @@ impl TryFrom<PyObject> for InternalDT {
                         value.getattr(py, "_type_")?.extract(py)?,
                     ))
                 })?;
-                Ok(InternalDT::Array(value, Box::new(ty_obj.try_into()?), len))
+                Ok(InternalDT::Array(
+                    value,
+                    Box::new(Self::from_pyobject_ctx(ty_obj, Some(endian))?),
+                    len,
+                    endian,
+                ))
             }
-            "Structure" => {
-                let fields = Python::with_gil(|py| {
-                    value
-                        .getattr(py, "_fields_")?
-                        .extract::<Vec<Vec<PyObject>>>(py)
-                })?;
+            "NdArray" => {
+                let (shape, ty_obj, strides_attr): (Vec<u32>, PyObject, Option<Vec<usize>>) =
+                    Python::with_gil(|py| {
+                        let shape: Vec<u32> = value.getattr(py, "_shape_")?.extract(py)?;
+                        let ty_obj: PyObject = value.getattr(py, "_type_")?.extract(py)?;
+                        let strides = match value.getattr(py, "_strides_") {
+                            Ok(val) => Some(val.extract::<Vec<usize>>(py)?),
+                            Err(_) => None,
+                        };
+                        Ok::<_, MemflowPyError>((shape, ty_obj, strides))
+                    })?;
 
-                // TODO: Clean this up with a zip iter (offset, field_tuple)
-                let mut current_offset = 0_usize;
-                let mut dt_fields = fields
-                    .into_iter()
-                    .map(|field| {
-                        let mut it = field.into_iter();
-                        let field_offset = current_offset;
-                        let field_name = it.next().unwrap().to_string();
-                        let field_type: InternalDT = it
-                            .next()
-                            .ok_or_else(|| MemflowPyError::NoType(field_name.clone()))?
-                            .try_into()?;
-                        current_offset += field_type.size();
-                        Ok((field_name, (field_offset, field_type)))
-                    })
-                    .collect::<Result<IndexMap<String, (usize, InternalDT)>, MemflowPyError>>()?;
-
-                // TODO: Clean this up
-                if let Some(offset_fields) = Python::with_gil::<
-                    _,
-                    Result<Option<IndexMap<String, (usize, InternalDT)>>, MemflowPyError>,
-                >(|py| {
-                    if let Ok(offsets_attr) = value.getattr(py, "_offsets_") {
-                        let offsets_obj = offsets_attr.extract::<Vec<Vec<PyObject>>>(py)?;
-
-                        let offset_fields = offsets_obj
-                        .into_iter()
-                        .map(|field| {
-                            let mut it = field.into_iter();
-                            let field_offset: usize = it.next().unwrap().extract(py)?;
-                            let field_name = it.next().unwrap().to_string();
-                            let field_type: InternalDT = it
-                                .next()
-                                .ok_or_else(|| MemflowPyError::NoType(field_name.clone()))?
-                                .try_into()?;
-                            Ok((field_name, (field_offset, field_type)))
-                        })
-                        .collect::<Result<IndexMap<String, (usize, InternalDT)>, MemflowPyError>>()?;
-
-                        Ok(Some(offset_fields))
-                    } else {
-                        Ok(None)
+                if let Some(strides) = &strides_attr {
+                    if strides.len() != shape.len() {
+                        return Err(MemflowPyError::InvalidType(format!(
+                            "_strides_ has {} entries but _shape_ has {}",
+                            strides.len(),
+                            shape.len()
+                        )));
                     }
-                })? {
-                    dt_fields.extend(offset_fields);
                 }
 
-                Ok(Self::Structure(value, dt_fields))
+                let elem_dt = Self::from_pyobject_ctx(ty_obj, Some(endian))?;
+                let elem_size = elem_dt.size();
+                // Contiguous row-major layout: stride[i] = prod(shape[i+1..]) * elem_size.
+                let strides = strides_attr.unwrap_or_else(|| {
+                    let mut strides = vec![0_usize; shape.len()];
+                    let mut acc = elem_size;
+                    for i in (0..shape.len()).rev() {
+                        strides[i] = acc;
+                        acc *= shape[i] as usize;
+                    }
+                    strides
+                });
+
+                Ok(Self::NdArray(value, Box::new(elem_dt), shape, strides))
+            }
+            "Structure" => {
+                let (dt_fields, align) = Self::parse_composite_fields(&value, endian, false)?;
+                let (dt_fields, align) = Self::merge_offset_fields(&value, endian, dt_fields, align)?;
+                Ok(Self::Structure(value, dt_fields, endian, align.max(1)))
+            }
+            "Union" => {
+                // Every field lives at offset 0; the union is as large and as
+                // aligned as its widest/most-aligned member.
+                let (dt_fields, align) = Self::parse_composite_fields(&value, endian, true)?;
+                Ok(Self::Structure(value, dt_fields, endian, align.max(1)))
             }
             _ => Err(MemflowPyError::InvalidType(base_name)),
         }
     }
+
+    /// Lays out a `Structure`/`Union`'s `_fields_` like ctypes/the C ABI would.
+    fn parse_composite_fields(
+        value: &PyObject,
+        endian: Endian,
+        is_union: bool,
+    ) -> Result<(IndexMap<String, StructField>, usize), MemflowPyError> {
+        let (fields, pack): (Vec<Vec<PyObject>>, Option<usize>) = Python::with_gil(|py| {
+            let fields = value
+                .getattr(py, "_fields_")?
+                .extract::<Vec<Vec<PyObject>>>(py)?;
+            let pack = match value.getattr(py, "_pack_") {
+                Ok(val) => Some(val.extract::<usize>(py)?),
+                Err(_) => None,
+            };
+            Ok::<_, MemflowPyError>((fields, pack))
+        })?;
+
+        let mut current_offset = 0_usize;
+        let mut max_align = 1_usize;
+        // (unit_offset, unit_size, next_free_bit) of the bitfield currently being packed.
+        let mut bitfield_unit: Option<(usize, usize, u8)> = None;
+        let mut dt_fields = IndexMap::new();
+
+        for field in fields {
+            let mut it = field.into_iter();
+            let field_name = it.next().unwrap().to_string();
+            let ty_obj = it
+                .next()
+                .ok_or_else(|| MemflowPyError::NoType(field_name.clone()))?;
+            let bit_width: Option<u8> = it
+                .next()
+                .map(|obj| Python::with_gil(|py| obj.extract::<u8>(py)))
+                .transpose()?;
+
+            let field_type = Self::from_pyobject_ctx(ty_obj, Some(endian))?;
+            let align = pack.map_or(field_type.alignment(), |p| field_type.alignment().min(p));
+            max_align = max_align.max(align);
+
+            let (field_offset, bitfield) = if is_union {
+                (0, bit_width.map(|bit_width| BitField {
+                    bit_offset: 0,
+                    bit_width,
+                }))
+            } else if let Some(bit_width) = bit_width {
+                let unit_size = field_type.size();
+                let reused = bitfield_unit.filter(|&(_, size, next_bit)| {
+                    size == unit_size && next_bit as usize + bit_width as usize <= unit_size * 8
+                });
+                let (unit_offset, next_bit) = match reused {
+                    Some((unit_offset, _, next_bit)) => (unit_offset, next_bit),
+                    None => {
+                        let unit_offset = round_up(current_offset, align);
+                        current_offset = unit_offset + unit_size;
+                        (unit_offset, 0)
+                    }
+                };
+                bitfield_unit = Some((unit_offset, unit_size, next_bit + bit_width));
+                (
+                    unit_offset,
+                    Some(BitField {
+                        bit_offset: next_bit,
+                        bit_width,
+                    }),
+                )
+            } else {
+                bitfield_unit = None;
+                let field_offset = round_up(current_offset, align);
+                current_offset = field_offset + field_type.size();
+                (field_offset, None)
+            };
+
+            dt_fields.insert(
+                field_name,
+                StructField {
+                    offset: field_offset,
+                    dt: field_type,
+                    bitfield,
+                },
+            );
+        }
+
+        Ok((dt_fields, max_align))
+    }
+
+    /// Merges the legacy `_offsets_` mechanism into an already-parsed field map.
+    fn merge_offset_fields(
+        value: &PyObject,
+        endian: Endian,
+        mut dt_fields: IndexMap<String, StructField>,
+        mut align: usize,
+    ) -> Result<(IndexMap<String, StructField>, usize), MemflowPyError> {
+        let offsets: Option<Vec<Vec<PyObject>>> =
+            Python::with_gil(
+                |py| match value.getattr(py, "_offsets_") {
+                    Ok(attr) => attr.extract(py).map(Some),
+                    Err(_) => Ok(None),
+                },
+            )?;
+        let pack: Option<usize> = Python::with_gil(|py| match value.getattr(py, "_pack_") {
+            Ok(val) => val.extract(py).map(Some),
+            Err(_) => Ok(None),
+        })?;
+
+        if let Some(offsets) = offsets {
+            for field in offsets {
+                let mut it = field.into_iter();
+                let field_offset: usize = Python::with_gil(|py| it.next().unwrap().extract(py))?;
+                let field_name = it.next().unwrap().to_string();
+                let field_type = Self::from_pyobject_ctx(
+                    it.next()
+                        .ok_or_else(|| MemflowPyError::NoType(field_name.clone()))?,
+                    Some(endian),
+                )?;
+                align = align.max(pack.map_or(field_type.alignment(), |p| field_type.alignment().min(p)));
+                dt_fields.insert(
+                    field_name,
+                    StructField {
+                        offset: field_offset,
+                        dt: field_type,
+                        bitfield: None,
+                    },
+                );
+            }
+        }
+
+        Ok((dt_fields, align))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn longdouble_round_trip(value: f64) -> f64 {
+        InternalDT::longdouble_decode(InternalDT::longdouble_encode(value))
+    }
+
+    #[test]
+    fn longdouble_round_trips_normal_magnitudes() {
+        for &value in &[1.0, -1.0, 123.456, -9.999e50, f64::MIN_POSITIVE, 1e-300, 1e300] {
+            let decoded = longdouble_round_trip(value);
+            let relative_error = ((decoded - value) / value).abs();
+            assert!(
+                relative_error < 1e-15,
+                "{value:e} round-tripped to {decoded:e}"
+            );
+        }
+    }
+
+    #[test]
+    fn longdouble_round_trips_f64_subnormals() {
+        let value = 5e-320_f64;
+        assert!(value.is_subnormal());
+        let decoded = longdouble_round_trip(value);
+        let relative_error = ((decoded - value) / value).abs();
+        assert!(
+            relative_error < 1e-10,
+            "{value:e} round-tripped to {decoded:e}"
+        );
+    }
+
+    #[test]
+    fn longdouble_round_trips_zero_inf_and_nan() {
+        assert_eq!(longdouble_round_trip(0.0), 0.0);
+        assert_eq!(longdouble_round_trip(-0.0), 0.0);
+        assert!(longdouble_round_trip(f64::INFINITY).is_infinite());
+        assert!(longdouble_round_trip(f64::NEG_INFINITY).is_infinite());
+        assert!(longdouble_round_trip(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn ndarray_strided_round_trips_contiguous_row_major() {
+        let shape = vec![2_u32, 3_u32];
+        let elem_size = 4;
+        // Contiguous row-major: stride[i] = prod(shape[i+1..]) * elem_size.
+        let strides = vec![3 * elem_size, elem_size];
+        let flat: Vec<u8> = (0..(2 * 3 * elem_size) as u8).collect();
+
+        let read_back = InternalDT::read_strided(&flat, &shape, &strides, elem_size);
+        assert_eq!(read_back, flat);
+
+        let mut written = vec![0_u8; flat.len()];
+        InternalDT::write_strided(&mut written, &flat, &shape, &strides, elem_size);
+        assert_eq!(written, flat);
+    }
+
+    #[test]
+    fn ndarray_strided_read_honors_transposed_view() {
+        // A 2x2 matrix of little-endian u32 words [0, 1, 2, 3] stored
+        // row-major; a transposed view swaps the strides and should read
+        // columns instead of rows without touching the source layout.
+        let shape = vec![2_u32, 2_u32];
+        let elem_size = 4;
+        let row_major_strides = vec![2 * elem_size, elem_size];
+        let transposed_strides = vec![elem_size, 2 * elem_size];
+        let flat: Vec<u8> = (0_u32..4)
+            .flat_map(|word| word.to_le_bytes())
+            .collect();
+
+        let as_words = |bytes: &[u8]| -> Vec<u32> {
+            bytes
+                .chunks(4)
+                .map(|c| u32::from_le_bytes(c.try_into().unwrap()))
+                .collect()
+        };
+
+        let row_major = InternalDT::read_strided(&flat, &shape, &row_major_strides, elem_size);
+        assert_eq!(as_words(&row_major), vec![0, 1, 2, 3]);
+
+        let transposed = InternalDT::read_strided(&flat, &shape, &transposed_strides, elem_size);
+        assert_eq!(as_words(&transposed), vec![0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn scalar_py_from_bytes_and_py_to_bytes_honor_endian_big() {
+        Python::with_gil(|py| {
+            let dt = InternalDT::Int(Endian::Big);
+            let bytes = 0x0102_0304_i32.to_be_bytes().to_vec();
+
+            let obj = dt.py_from_bytes(bytes.clone()).unwrap();
+            let value: i32 = obj.extract(py).unwrap();
+            assert_eq!(value, 0x0102_0304);
+
+            assert_eq!(dt.py_to_bytes(obj).unwrap(), bytes);
+        });
+    }
+
+    #[test]
+    fn nested_structure_and_array_py_from_bytes_and_py_to_bytes_honor_endian_big() {
+        Python::with_gil(|py| {
+            let locals = PyDict::new(py);
+            py.run(
+                r#"
+import ctypes
+
+class Big(ctypes.Structure):
+    _endian_ = "big"
+    _fields_ = [
+        ("scalar", ctypes.c_int32),
+        ("arr", ctypes.c_int32 * 2),
+    ]
+"#,
+                None,
+                Some(locals),
+            )
+            .unwrap();
+
+            let class: PyObject = locals.get_item("Big").unwrap().unwrap().into();
+            let dt = InternalDT::from_pyobject_ctx(class, None).unwrap();
+
+            let mut bytes = 0x1122_3344_i32.to_be_bytes().to_vec();
+            bytes.extend(10_i32.to_be_bytes());
+            bytes.extend(20_i32.to_be_bytes());
+
+            let obj = dt.py_from_bytes(bytes.clone()).unwrap();
+            let scalar: i32 = obj.getattr(py, "scalar").unwrap().extract(py).unwrap();
+            assert_eq!(scalar, 0x1122_3344);
+            let arr = obj.getattr(py, "arr").unwrap();
+            let first: i32 = arr.call_method1(py, "__getitem__", (0,)).unwrap().extract(py).unwrap();
+            assert_eq!(first, 10);
+
+            assert_eq!(dt.py_to_bytes(obj).unwrap(), bytes);
+        });
+    }
+
+    #[test]
+    fn ndarray_py_from_bytes_and_py_to_bytes_round_trip_through_numpy() {
+        Python::with_gil(|py| {
+            let locals = PyDict::new(py);
+            py.run(
+                r#"
+import ctypes
+
+class NdArray:
+    pass
+
+class Matrix(NdArray):
+    _shape_ = (2, 2)
+    _type_ = ctypes.c_int32
+"#,
+                None,
+                Some(locals),
+            )
+            .unwrap();
+
+            let class: PyObject = locals.get_item("Matrix").unwrap().unwrap().into();
+            let dt = InternalDT::from_pyobject_ctx(class, None).unwrap();
+
+            let flat: Vec<u8> = (0_i32..4).flat_map(|n| n.to_le_bytes()).collect();
+            let array = dt.py_from_bytes(flat.clone()).unwrap();
+
+            let as_list: Vec<Vec<i32>> = array.call_method0(py, "tolist").unwrap().extract(py).unwrap();
+            assert_eq!(as_list, vec![vec![0, 1], vec![2, 3]]);
+
+            let round_tripped = dt.py_to_bytes(array).unwrap();
+            assert_eq!(round_tripped, flat);
+        });
+    }
+
+    #[test]
+    fn parse_composite_fields_puts_every_union_field_at_offset_zero() {
+        Python::with_gil(|py| {
+            let locals = PyDict::new(py);
+            py.run(
+                r#"
+import ctypes
+
+class Overlay(ctypes.Union):
+    _fields_ = [
+        ("byte", ctypes.c_uint8),
+        ("word", ctypes.c_uint32),
+    ]
+"#,
+                None,
+                Some(locals),
+            )
+            .unwrap();
+
+            let class: PyObject = locals.get_item("Overlay").unwrap().unwrap().into();
+            let dt = InternalDT::from_pyobject_ctx(class, None).unwrap();
+            let size = dt.size();
+            let (fields, align) = match dt {
+                InternalDT::Structure(_, fields, _, align) => (fields, align),
+                other => panic!("expected Structure, got {:?}", other),
+            };
+
+            assert_eq!(fields["byte"].offset, 0);
+            assert_eq!(fields["word"].offset, 0);
+            assert_eq!(align, 4);
+            assert_eq!(size, 4);
+        });
+    }
+
+    #[test]
+    fn parse_composite_fields_caps_alignment_and_bitfield_units_to_pack() {
+        Python::with_gil(|py| {
+            let locals = PyDict::new(py);
+            py.run(
+                r#"
+import ctypes
+
+class Packed(ctypes.Structure):
+    _pack_ = 1
+    _fields_ = [
+        ("a", ctypes.c_uint8),
+        ("b", ctypes.c_uint32, 3),
+        ("c", ctypes.c_uint32, 5),
+        ("d", ctypes.c_uint32),
+    ]
+"#,
+                None,
+                Some(locals),
+            )
+            .unwrap();
+
+            let class: PyObject = locals.get_item("Packed").unwrap().unwrap().into();
+            let dt = InternalDT::from_pyobject_ctx(class, None).unwrap();
+            let size = dt.size();
+            let (fields, align) = match dt {
+                InternalDT::Structure(_, fields, _, align) => (fields, align),
+                other => panic!("expected Structure, got {:?}", other),
+            };
+
+            // With `_pack_ = 1` every field's alignment is capped to 1, so
+            // the `b`/`c` bitfield unit starts right after `a` instead of
+            // being aligned out to 4, and the struct itself is byte-packed.
+            assert_eq!(fields["a"].offset, 0);
+            assert_eq!(fields["b"].offset, 1);
+            assert_eq!(fields["c"].offset, 1);
+            assert_eq!(fields["d"].offset, 5);
+            assert_eq!(align, 1);
+            assert_eq!(size, 9);
+        });
+    }
+
+    #[test]
+    fn parse_composite_fields_packs_bitfields_and_pads_to_known_layout() {
+        Python::with_gil(|py| {
+            let locals = PyDict::new(py);
+            py.run(
+                r#"
+import ctypes
+
+class Flags(ctypes.Structure):
+    _fields_ = [
+        ("a", ctypes.c_uint8),
+        ("b", ctypes.c_uint32, 3),
+        ("c", ctypes.c_uint32, 5),
+        ("d", ctypes.c_uint16),
+    ]
+"#,
+                None,
+                Some(locals),
+            )
+            .unwrap();
+
+            let class: PyObject = locals.get_item("Flags").unwrap().unwrap().into();
+            let dt = InternalDT::from_pyobject_ctx(class, None).unwrap();
+            let (fields, align) = match dt {
+                InternalDT::Structure(_, fields, _, align) => (fields, align),
+                other => panic!("expected Structure, got {:?}", other),
+            };
+
+            // `a` (u8) sits at offset 0; `b`/`c` share one 4-byte bitfield
+            // storage unit aligned to 4 right after it; `d` (u16), not being a
+            // bitfield, follows at the next 2-byte boundary after that unit.
+            assert_eq!(fields["a"].offset, 0);
+            assert_eq!(fields["b"].offset, 4);
+            let b_bits = fields["b"].bitfield.as_ref().unwrap();
+            assert_eq!(b_bits.bit_offset, 0);
+            assert_eq!(b_bits.bit_width, 3);
+            assert_eq!(fields["c"].offset, 4);
+            let c_bits = fields["c"].bitfield.as_ref().unwrap();
+            assert_eq!(c_bits.bit_offset, 3);
+            assert_eq!(c_bits.bit_width, 5);
+            assert_eq!(fields["d"].offset, 8);
+            assert_eq!(align, 4);
+        });
+    }
 }